@@ -0,0 +1,51 @@
+//! DHCP message type module (option 53).
+
+use std::fmt;
+
+/// The DHCP message type carried in option 53.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageType {
+    Undefined,
+    Discover,
+    Offer,
+    Request,
+    Decline,
+    Ack,
+    Nak,
+    Release,
+    Inform,
+}
+
+impl From<u8> for MessageType {
+    fn from(value: u8) -> Self {
+        use self::MessageType::*;
+        match value {
+            1 => Discover,
+            2 => Offer,
+            3 => Request,
+            4 => Decline,
+            5 => Ack,
+            6 => Nak,
+            7 => Release,
+            8 => Inform,
+            _ => Undefined,
+        }
+    }
+}
+
+impl fmt::Display for MessageType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::MessageType::*;
+        match self {
+            Undefined => write!(f, "UNDEFINED"),
+            Discover => write!(f, "DHCPDISCOVER"),
+            Offer => write!(f, "DHCPOFFER"),
+            Request => write!(f, "DHCPREQUEST"),
+            Decline => write!(f, "DHCPDECLINE"),
+            Ack => write!(f, "DHCPACK"),
+            Nak => write!(f, "DHCPNAK"),
+            Release => write!(f, "DHCPRELEASE"),
+            Inform => write!(f, "DHCPINFORM"),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! DHCP option codes and values.
+
+/// A DHCP option code (RFC 2132 and friends).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum OptionCode {
+    /// Option 114 (RFC 7710): captive portal API URL.
+    CaptivePortalUrl,
+    /// Any option code this crate does not interpret structurally.
+    Other(u8),
+}
+
+impl From<u8> for OptionCode {
+    fn from(value: u8) -> Self {
+        match value {
+            114 => OptionCode::CaptivePortalUrl,
+            other => OptionCode::Other(other),
+        }
+    }
+}
+
+impl From<OptionCode> for u8 {
+    fn from(code: OptionCode) -> Self {
+        match code {
+            OptionCode::CaptivePortalUrl => 114,
+            OptionCode::Other(value) => value,
+        }
+    }
+}
+
+/// A single DHCP option and its decoded value, as carried on the wire.
+#[derive(Debug, Clone)]
+pub enum DhcpOption {
+    /// Option 114 (RFC 7710): the captive portal API URL, a UTF-8 URI with no length
+    /// prefix beyond the standard option length byte.
+    CaptivePortalUrl(String),
+    /// Any option this crate does not interpret, carried as raw bytes.
+    Other(OptionCode, Vec<u8>),
+}
+
+impl DhcpOption {
+    /// Returns this option's code.
+    pub fn code(&self) -> OptionCode {
+        match self {
+            DhcpOption::CaptivePortalUrl(_) => OptionCode::CaptivePortalUrl,
+            DhcpOption::Other(code, _) => *code,
+        }
+    }
+
+    /// Decodes a single option from its raw wire value.
+    pub fn decode(code: OptionCode, value: &[u8]) -> Self {
+        match code {
+            OptionCode::CaptivePortalUrl => match String::from_utf8(value.to_vec()) {
+                Ok(url) => DhcpOption::CaptivePortalUrl(url),
+                Err(_) => DhcpOption::Other(code, value.to_vec()),
+            },
+            _ => DhcpOption::Other(code, value.to_vec()),
+        }
+    }
+
+    /// Encodes this option's value to its raw wire representation.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            DhcpOption::CaptivePortalUrl(url) => url.clone().into_bytes(),
+            DhcpOption::Other(_, bytes) => bytes.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_code_114_round_trips_to_captive_portal_url() {
+        assert_eq!(OptionCode::from(114), OptionCode::CaptivePortalUrl);
+        assert_eq!(u8::from(OptionCode::CaptivePortalUrl), 114);
+    }
+
+    #[test]
+    fn decoding_option_114_yields_the_url() {
+        let option = DhcpOption::decode(OptionCode::CaptivePortalUrl, b"https://portal.example");
+
+        match option {
+            DhcpOption::CaptivePortalUrl(ref url) => assert_eq!(url, "https://portal.example"),
+            ref other => panic!("expected CaptivePortalUrl, got {:?}", other),
+        }
+        assert_eq!(option.encode(), b"https://portal.example".to_vec());
+    }
+
+    #[test]
+    fn decoding_invalid_utf8_for_option_114_falls_back_to_raw_bytes() {
+        let invalid_utf8 = vec![0xff, 0xfe];
+        let option = DhcpOption::decode(OptionCode::CaptivePortalUrl, &invalid_utf8);
+
+        match option {
+            DhcpOption::Other(OptionCode::CaptivePortalUrl, ref bytes) => assert_eq!(bytes, &invalid_utf8),
+            ref other => panic!("expected Other(CaptivePortalUrl, ..), got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unrecognized_option_codes_round_trip_as_raw_bytes() {
+        let option = DhcpOption::decode(OptionCode::from(51), &[0, 0, 1, 0]);
+
+        assert_eq!(option.code(), OptionCode::Other(51));
+        assert_eq!(option.encode(), vec![0, 0, 1, 0]);
+    }
+}
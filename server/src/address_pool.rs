@@ -0,0 +1,352 @@
+//! The subsystem owning the set of addresses the server is allowed to assign.
+
+use std::collections::BTreeSet;
+use std::net::Ipv4Addr;
+
+use storage::{Error as StorageError, Storage};
+
+/// Errors generated by the `AddressPool`.
+#[derive(Fail, Debug)]
+pub enum Error {
+    #[fail(display = "Address pool exhausted")]
+    PoolExhausted,
+    #[fail(display = "Address {} is not managed by this pool", _0)]
+    AddressNotManaged(Ipv4Addr),
+    #[fail(display = "Address {} is frozen", _0)]
+    AddressFrozen(Ipv4Addr),
+    #[fail(display = "Storage error: {}", _0)]
+    Storage(StorageError),
+}
+
+impl From<StorageError> for Error {
+    fn from(error: StorageError) -> Self {
+        Error::Storage(error)
+    }
+}
+
+/// Owns the set of assignable addresses and decides which one to hand out next.
+///
+/// Tracks allocated, available and frozen addresses in `BTreeSet`s over a managed range
+/// of `Ipv4Addr`, taking storage lease/frozen state into account so implementers of
+/// `Storage` no longer have to pick addresses themselves.
+pub struct AddressPool {
+    available   : BTreeSet<Ipv4Addr>,
+    allocated   : BTreeSet<Ipv4Addr>,
+    frozen      : BTreeSet<Ipv4Addr>,
+}
+
+impl AddressPool {
+    /// Builds a pool managing the contiguous range `[first, last]`.
+    pub fn new(first: Ipv4Addr, last: Ipv4Addr) -> Self {
+        let first = u32::from(first);
+        let last = u32::from(last);
+        Self::from_addresses((first..=last).map(Ipv4Addr::from))
+    }
+
+    /// Builds a pool managing an explicit, possibly non-contiguous, set of addresses.
+    pub fn from_addresses<I: IntoIterator<Item = Ipv4Addr>>(addresses: I) -> Self {
+        AddressPool {
+            available   : addresses.into_iter().collect(),
+            allocated   : BTreeSet::new(),
+            frozen      : BTreeSet::new(),
+        }
+    }
+
+    /// Allocates the next free address for `client_id`.
+    ///
+    /// Prefers, in order:
+    /// 1. `requested_ip`, if it is managed by the pool, not allocated and not frozen;
+    /// 2. the client's previously-leased address (`previous`, from `Storage::get_lease`),
+    ///    for lease stability, under the same conditions;
+    /// 3. the lowest free address in the pool.
+    ///
+    /// # Errors
+    /// Returns `Error::PoolExhausted` if there is no free address left to hand out.
+    pub fn allocate(
+        &mut self,
+        requested_ip: Option<Ipv4Addr>,
+        previous: Option<Ipv4Addr>,
+    ) -> Result<Ipv4Addr, Error> {
+        if let Some(address) = requested_ip {
+            if self.is_free(&address) {
+                return Ok(self.take(address));
+            }
+        }
+
+        if let Some(address) = previous {
+            if self.is_free(&address) {
+                return Ok(self.take(address));
+            }
+        }
+
+        let address = *self.available.iter().next().ok_or(Error::PoolExhausted)?;
+        Ok(self.take(address))
+    }
+
+    /// Returns `true` if `address` is managed by the pool, currently unallocated and
+    /// not frozen.
+    pub fn is_free(&self, address: &Ipv4Addr) -> bool {
+        self.available.contains(address) && !self.frozen.contains(address)
+    }
+
+    /// Reclaims an expired lease's address, returning it to the available set unless
+    /// it has since been frozen.
+    pub fn reclaim(&mut self, address: Ipv4Addr) {
+        self.allocated.remove(&address);
+        if !self.frozen.contains(&address) {
+            self.available.insert(address);
+        }
+    }
+
+    /// Marks `address` as frozen, excluding it from allocation until `unfreeze` is called.
+    ///
+    /// Also marks the address frozen in `storage` via `Storage::add_frozen`, so the
+    /// pool's in-memory frozen set and the persistent frozen state recorded by
+    /// `Storage::check_frozen` never diverge after a DHCPDECLINE.
+    ///
+    /// # Errors
+    /// Returns `Error::AddressNotManaged` if the address is outside the managed range,
+    /// `Error::AddressFrozen` if it is already frozen, or `Error::Storage` if `storage`
+    /// fails to record the freeze.
+    pub fn freeze<S: Storage>(&mut self, address: Ipv4Addr, storage: &mut S) -> Result<(), Error> {
+        if self.frozen.contains(&address) {
+            return Err(Error::AddressFrozen(address));
+        }
+        if !self.available.contains(&address) && !self.allocated.contains(&address) {
+            return Err(Error::AddressNotManaged(address));
+        }
+        storage.add_frozen(&address)?;
+        self.available.remove(&address);
+        self.allocated.remove(&address);
+        self.frozen.insert(address);
+        Ok(())
+    }
+
+    /// Returns a frozen address to the available set.
+    ///
+    /// Also clears the frozen mark in `storage` via `Storage::remove_frozen`, keeping
+    /// it in sync with the pool's in-memory frozen set.
+    ///
+    /// # Errors
+    /// Returns `Error::Storage` if `storage` fails to clear the freeze.
+    pub fn unfreeze<S: Storage>(&mut self, address: Ipv4Addr, storage: &mut S) -> Result<(), Error> {
+        if self.frozen.contains(&address) {
+            storage.remove_frozen(&address)?;
+            self.frozen.remove(&address);
+            self.available.insert(address);
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, address: Ipv4Addr) -> Ipv4Addr {
+        self.available.remove(&address);
+        self.allocated.insert(address);
+        address
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use lease::Lease;
+    use storage::Error as StorageError;
+
+    /// A `Storage` that only tracks frozen addresses, for exercising `AddressPool`'s
+    /// freeze/unfreeze sync without needing a real lease database.
+    #[derive(Default)]
+    struct FrozenOnlyStorage {
+        frozen: BTreeSet<Ipv4Addr>,
+    }
+
+    impl Storage for FrozenOnlyStorage {
+        fn get_client(&self, _address: &Ipv4Addr) -> Result<Option<Vec<u8>>, StorageError> {
+            Ok(None)
+        }
+
+        fn add_client(&mut self, _address: &Ipv4Addr, _client_id: &[u8]) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn delete_client(&mut self, _address: &Ipv4Addr) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_lease(&self, _client_id: &[u8]) -> Result<Option<Lease>, StorageError> {
+            Ok(None)
+        }
+
+        fn add_lease(&mut self, _client_id: &[u8], _lease: Lease) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn update_lease(
+            &mut self,
+            _client_id: &[u8],
+            _action: &mut FnMut(&mut Lease) -> (),
+        ) -> Result<Option<Lease>, StorageError> {
+            Ok(None)
+        }
+
+        fn check_frozen(&self, address: &Ipv4Addr) -> Result<bool, StorageError> {
+            Ok(self.frozen.contains(address))
+        }
+
+        fn add_frozen(&mut self, address: &Ipv4Addr) -> Result<(), StorageError> {
+            self.frozen.insert(*address);
+            Ok(())
+        }
+
+        fn remove_frozen(&mut self, address: &Ipv4Addr) -> Result<(), StorageError> {
+            self.frozen.remove(address);
+            Ok(())
+        }
+    }
+
+    /// A `Storage` whose `add_frozen`/`remove_frozen` always fail, for exercising
+    /// `AddressPool`'s error paths.
+    #[derive(Default)]
+    struct FailingStorage;
+
+    impl Storage for FailingStorage {
+        fn get_client(&self, _address: &Ipv4Addr) -> Result<Option<Vec<u8>>, StorageError> {
+            Ok(None)
+        }
+
+        fn add_client(&mut self, _address: &Ipv4Addr, _client_id: &[u8]) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn delete_client(&mut self, _address: &Ipv4Addr) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn get_lease(&self, _client_id: &[u8]) -> Result<Option<Lease>, StorageError> {
+            Ok(None)
+        }
+
+        fn add_lease(&mut self, _client_id: &[u8], _lease: Lease) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        fn update_lease(
+            &mut self,
+            _client_id: &[u8],
+            _action: &mut FnMut(&mut Lease) -> (),
+        ) -> Result<Option<Lease>, StorageError> {
+            Ok(None)
+        }
+
+        fn check_frozen(&self, _address: &Ipv4Addr) -> Result<bool, StorageError> {
+            Ok(false)
+        }
+
+        fn add_frozen(&mut self, _address: &Ipv4Addr) -> Result<(), StorageError> {
+            Err(StorageError::Other("storage unavailable".to_owned()))
+        }
+
+        fn remove_frozen(&mut self, _address: &Ipv4Addr) -> Result<(), StorageError> {
+            Err(StorageError::Other("storage unavailable".to_owned()))
+        }
+    }
+
+    fn range(first: u8, last: u8) -> AddressPool {
+        AddressPool::new(Ipv4Addr::new(192, 168, 0, first), Ipv4Addr::new(192, 168, 0, last))
+    }
+
+    #[test]
+    fn allocate_prefers_requested_ip_over_previous_and_lowest_free() {
+        let mut pool = range(1, 3);
+        let requested = Ipv4Addr::new(192, 168, 0, 3);
+        let previous = Ipv4Addr::new(192, 168, 0, 2);
+
+        let allocated = pool.allocate(Some(requested), Some(previous)).unwrap();
+
+        assert_eq!(allocated, requested);
+    }
+
+    #[test]
+    fn allocate_prefers_previous_over_lowest_free_when_requested_is_unavailable() {
+        let mut pool = range(1, 3);
+        let requested = Ipv4Addr::new(192, 168, 0, 3);
+        let previous = Ipv4Addr::new(192, 168, 0, 2);
+        pool.allocate(Some(requested), None).unwrap();
+
+        let allocated = pool.allocate(Some(requested), Some(previous)).unwrap();
+
+        assert_eq!(allocated, previous);
+    }
+
+    #[test]
+    fn allocate_falls_back_to_lowest_free_address() {
+        let mut pool = range(1, 3);
+
+        let allocated = pool.allocate(None, None).unwrap();
+
+        assert_eq!(allocated, Ipv4Addr::new(192, 168, 0, 1));
+    }
+
+    #[test]
+    fn allocate_returns_pool_exhausted_once_every_address_is_taken() {
+        let mut pool = range(1, 2);
+        pool.allocate(None, None).unwrap();
+        pool.allocate(None, None).unwrap();
+
+        let result = pool.allocate(None, None);
+
+        match result {
+            Err(Error::PoolExhausted) => {}
+            other => panic!("expected PoolExhausted, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn freeze_marks_storage_frozen_and_is_idempotent() {
+        let mut pool = range(1, 1);
+        let mut storage = FrozenOnlyStorage::default();
+        let address = Ipv4Addr::new(192, 168, 0, 1);
+
+        pool.freeze(address, &mut storage).unwrap();
+
+        assert!(storage.check_frozen(&address).unwrap());
+        assert!(!pool.is_free(&address));
+
+        match pool.freeze(address, &mut storage) {
+            Err(Error::AddressFrozen(a)) => assert_eq!(a, address),
+            other => panic!("expected AddressFrozen, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unfreeze_clears_storage_frozen_and_is_idempotent() {
+        let mut pool = range(1, 1);
+        let mut storage = FrozenOnlyStorage::default();
+        let address = Ipv4Addr::new(192, 168, 0, 1);
+
+        pool.freeze(address, &mut storage).unwrap();
+        pool.unfreeze(address, &mut storage).unwrap();
+
+        assert!(!storage.check_frozen(&address).unwrap());
+        assert!(pool.is_free(&address));
+
+        // Unfreezing an address that isn't frozen is a no-op, not an error.
+        pool.unfreeze(address, &mut storage).unwrap();
+    }
+
+    #[test]
+    fn unfreeze_leaves_address_frozen_when_storage_fails() {
+        let mut pool = range(1, 1);
+        let mut good_storage = FrozenOnlyStorage::default();
+        let address = Ipv4Addr::new(192, 168, 0, 1);
+        pool.freeze(address, &mut good_storage).unwrap();
+
+        let mut failing_storage = FailingStorage::default();
+        let result = pool.unfreeze(address, &mut failing_storage);
+
+        assert!(result.is_err());
+        // The address must not vanish from every set when storage errors: it should
+        // still be frozen, not silently dropped into neither `frozen` nor `available`.
+        assert!(!pool.is_free(&address));
+        assert!(pool.freeze(address, &mut good_storage).is_err(), "address should still be frozen");
+    }
+}
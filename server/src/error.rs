@@ -0,0 +1,66 @@
+//! Structured errors produced while processing an inbound DHCP message.
+
+use std::net::Ipv4Addr;
+
+use protocol::message::message_type::MessageType;
+
+use address_pool::Error as AddressPoolError;
+
+/// Errors generated while servicing a single client message.
+///
+/// Distinct from `storage::Error`, which only covers the persistence layer: this type
+/// discriminates the ways a request itself can fail to be serviced, so callers can log,
+/// silently drop, or NAK as appropriate.
+#[derive(Fail, Debug)]
+pub enum ServerError {
+    #[fail(display = "The server cannot service a message of type {}", _0)]
+    InvalidClientMessage(MessageType),
+    #[fail(display = "The client requested an address not available to it: {}", _0)]
+    BadRequestedIpv4Addr(Ipv4Addr),
+    #[fail(display = "Address pool failure: {}", _0)]
+    ServerAddressPoolFailure(AddressPoolError),
+    #[fail(display = "The message's server identifier does not match this server")]
+    UnwantedDHCPServer,
+}
+
+impl From<AddressPoolError> for ServerError {
+    fn from(error: AddressPoolError) -> Self {
+        ServerError::ServerAddressPoolFailure(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_pool_failure_converts_into_server_error() {
+        let error: ServerError = AddressPoolError::PoolExhausted.into();
+
+        match error {
+            ServerError::ServerAddressPoolFailure(AddressPoolError::PoolExhausted) => {}
+            other => panic!("expected ServerAddressPoolFailure(PoolExhausted), got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invalid_client_message_mentions_the_offending_message_type() {
+        let error = ServerError::InvalidClientMessage(MessageType::Decline);
+
+        assert_eq!(
+            error.to_string(),
+            "The server cannot service a message of type DHCPDECLINE"
+        );
+    }
+
+    #[test]
+    fn bad_requested_ipv4_addr_mentions_the_address() {
+        let address = Ipv4Addr::new(192, 168, 0, 42);
+        let error = ServerError::BadRequestedIpv4Addr(address);
+
+        assert_eq!(
+            error.to_string(),
+            "The client requested an address not available to it: 192.168.0.42"
+        );
+    }
+}
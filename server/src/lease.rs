@@ -0,0 +1,11 @@
+//! The lease record a `Storage` implementation stores for a single client.
+
+use std::net::Ipv4Addr;
+use std::time::SystemTime;
+
+/// A client's current address lease.
+#[derive(Debug, Clone)]
+pub struct Lease {
+    pub address     : Ipv4Addr,
+    pub expires     : SystemTime,
+}
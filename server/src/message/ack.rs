@@ -1,8 +1,49 @@
 use std::net::Ipv4Addr;
 
+use protocol::message::options::DhcpOption;
+
 #[derive(Debug)]
 pub struct Ack {
-    pub address     : Ipv4Addr,
-    pub lease_time  : u32,
-    pub message     : String,
+    pub address         : Ipv4Addr,
+    pub lease_time      : u32,
+    pub message         : String,
+    /// The captive portal API URL advertised via option 114 (RFC 7710), if the server sent one.
+    pub captive_url     : Option<String>,
+}
+
+impl Ack {
+    /// Builds an `Ack` from the reply's fixed fields and decoded options, populating
+    /// `captive_url` from option 114 only if the server included it.
+    pub fn new(address: Ipv4Addr, lease_time: u32, message: String, options: &[DhcpOption]) -> Self {
+        Ack {
+            address,
+            lease_time,
+            message,
+            captive_url: options.iter().find_map(|option| match option {
+                DhcpOption::CaptivePortalUrl(url) => Some(url.clone()),
+                _ => None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captive_url_is_none_when_option_114_is_absent() {
+        let ack = Ack::new(Ipv4Addr::new(192, 168, 0, 1), 3600, "ack".to_owned(), &[]);
+
+        assert_eq!(ack.captive_url, None);
+    }
+
+    #[test]
+    fn captive_url_is_populated_when_option_114_is_present() {
+        let options = vec![DhcpOption::CaptivePortalUrl("https://portal.example".to_owned())];
+
+        let ack = Ack::new(Ipv4Addr::new(192, 168, 0, 1), 3600, "ack".to_owned(), &options);
+
+        assert_eq!(ack.captive_url, Some("https://portal.example".to_owned()));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,93 @@
+//! Configurable repository of DHCP options to emit in OFFER/ACK replies.
+
+use std::collections::HashMap;
+
+use protocol::message::options::{DhcpOption, OptionCode};
+
+/// Holds operator-configured options (NTP servers, domain search list, TFTP/bootfile,
+/// a captive portal URL via `DhcpOption::CaptivePortalUrl` (RFC 7710, option 114),
+/// vendor-specific options, ...) to be merged into every OFFER/ACK the server sends,
+/// in addition to the per-request computed options (address, lease time, subnet).
+#[derive(Default)]
+pub struct OptionsRepo {
+    options: HashMap<OptionCode, DhcpOption>,
+}
+
+impl OptionsRepo {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        OptionsRepo {
+            options: HashMap::new(),
+        }
+    }
+
+    /// Registers an option to be sent with every reply, replacing any previous value
+    /// for the same code.
+    pub fn insert(&mut self, option: DhcpOption) {
+        self.options.insert(option.code(), option);
+    }
+
+    /// Stops sending the option with the given code.
+    pub fn remove(&mut self, code: OptionCode) -> Option<DhcpOption> {
+        self.options.remove(&code)
+    }
+
+    /// Merges the repository's options with the per-request computed ones, with the
+    /// computed ones taking precedence where both set the same code.
+    pub fn merge(&self, computed: Vec<DhcpOption>) -> Vec<DhcpOption> {
+        let mut merged = self.options.clone();
+        for option in computed {
+            merged.insert(option.code(), option);
+        }
+        merged.into_iter().map(|(_, option)| option).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(options: &[DhcpOption]) -> Vec<OptionCode> {
+        let mut codes: Vec<OptionCode> = options.iter().map(DhcpOption::code).collect();
+        codes.sort();
+        codes
+    }
+
+    #[test]
+    fn insert_then_remove_stops_sending_the_option() {
+        let mut repo = OptionsRepo::new();
+        repo.insert(DhcpOption::CaptivePortalUrl("https://portal.example".to_owned()));
+
+        assert_eq!(codes(&repo.merge(Vec::new())), vec![OptionCode::CaptivePortalUrl]);
+
+        repo.remove(OptionCode::CaptivePortalUrl);
+
+        assert!(repo.merge(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn merge_includes_both_configured_and_computed_options() {
+        let mut repo = OptionsRepo::new();
+        repo.insert(DhcpOption::CaptivePortalUrl("https://portal.example".to_owned()));
+
+        let computed = vec![DhcpOption::Other(OptionCode::Other(51), vec![0, 0, 1, 0])];
+        let merged = codes(&repo.merge(computed));
+
+        assert_eq!(merged, vec![OptionCode::CaptivePortalUrl, OptionCode::Other(51)]);
+    }
+
+    #[test]
+    fn merge_lets_computed_options_override_configured_ones_with_the_same_code() {
+        let mut repo = OptionsRepo::new();
+        repo.insert(DhcpOption::CaptivePortalUrl("https://configured.example".to_owned()));
+
+        let computed = vec![DhcpOption::CaptivePortalUrl("https://computed.example".to_owned())];
+        let merged = repo.merge(computed);
+
+        assert_eq!(merged.len(), 1);
+        match &merged[0] {
+            DhcpOption::CaptivePortalUrl(url) => assert_eq!(url, "https://computed.example"),
+            other => panic!("expected CaptivePortalUrl, got {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,11 @@
 //! The trait user must implement to provide a persistent lease storage for the DHCP server.
 
 use std::net::Ipv4Addr;
+use std::time::Duration;
 
+use address_pool::AddressPool;
 use lease::Lease;
+use time::SystemTimeSource;
 
 /// Errors generated by the `Storage` trait methods.
 #[derive(Fail, Debug)]
@@ -25,6 +28,8 @@ pub enum Error {
     CheckFrozen(String),
     #[fail(display = "Frozen address adding error: {}", _0)]
     AddFrozen(String),
+    #[fail(display = "Frozen address removing error: {}", _0)]
+    RemoveFrozen(String),
 
     #[fail(display = "Another error: {}", _0)]
     Other(String),
@@ -121,4 +126,280 @@ where
         &mut self,
         address: &Ipv4Addr,
     ) -> Result<(), Error>;
+
+    /// Must remove the frozen mark from the given address, the counterpart to
+    /// `add_frozen`.
+    ///
+    /// # Errors
+    /// Must return `Error::RemoveFrozen(desc)` if there is a database I/O error
+    /// or `Error::Other(desc)` if you want to report another error (e.g. connection).
+    fn remove_frozen(
+        &mut self,
+        address: &Ipv4Addr,
+    ) -> Result<(), Error>;
+
+    /// Administratively releases the lease held by the given client, disassociating it
+    /// from its address and reclaiming the address into `pool` so it immediately
+    /// returns to the pool rather than staying stuck in `pool`'s allocated set.
+    ///
+    /// Does nothing if the client has no lease.
+    ///
+    /// # Errors
+    /// Must return `Error::GetLease(desc)` or `Error::DeleteClient(desc)` if there is
+    /// a database I/O error, or `Error::Other(desc)` if you want to report another error.
+    fn release_lease(
+        &mut self,
+        client_id: &[u8],
+        pool: &mut AddressPool,
+    ) -> Result<(), Error> {
+        if let Some(lease) = self.get_lease(client_id)? {
+            self.delete_client(&lease.address)?;
+            pool.reclaim(lease.address);
+        }
+        Ok(())
+    }
+
+    /// Administratively releases whatever lease is bound to the given address, without
+    /// needing the client ID, and reclaims the address into `pool`.
+    ///
+    /// # Errors
+    /// Must return `Error::DeleteClient(desc)` if there is a database I/O error
+    /// or `Error::Other(desc)` if you want to report another error (e.g. connection).
+    fn release_lease_by_address(
+        &mut self,
+        address: &Ipv4Addr,
+        pool: &mut AddressPool,
+    ) -> Result<(), Error> {
+        self.delete_client(address)?;
+        pool.reclaim(*address);
+        Ok(())
+    }
+
+    /// Administratively extends the expiry of the lease held by the given client by
+    /// `extend_by`, measured from the time source's current time.
+    ///
+    /// # Errors
+    /// Must return `Error::UpdateLease(desc)` if there is a database I/O error
+    /// or `Error::Other(desc)` if you want to report another error (e.g. connection).
+    fn renew_lease(
+        &mut self,
+        client_id: &[u8],
+        time_source: &SystemTimeSource,
+        extend_by: Duration,
+    ) -> Result<Option<Lease>, Error> {
+        let now = time_source.now();
+        self.update_lease(client_id, &mut |lease| {
+            lease.expires = now + extend_by;
+        })
+    }
+
+    /// Administratively forces the lease held by the given client into an expired state
+    /// as of the time source's current time, leaving the client-address association and
+    /// the address's place in `AddressPool`'s allocated set unchanged.
+    ///
+    /// # Errors
+    /// Must return `Error::UpdateLease(desc)` if there is a database I/O error
+    /// or `Error::Other(desc)` if you want to report another error (e.g. connection).
+    fn force_expire_lease(
+        &mut self,
+        client_id: &[u8],
+        time_source: &SystemTimeSource,
+    ) -> Result<Option<Lease>, Error> {
+        let now = time_source.now();
+        self.update_lease(client_id, &mut |lease| {
+            lease.expires = now;
+        })
+    }
+
+    /// Administratively deletes the lease held by the given client: forces it to expire
+    /// as of now, then disassociates the client from its address, reclaiming the address
+    /// into `pool` immediately rather than waiting for reclamation. The lease record
+    /// itself is not removed from storage; it remains, expired as of now.
+    ///
+    /// # Errors
+    /// Must return `Error::GetLease(desc)`, `Error::UpdateLease(desc)` or
+    /// `Error::DeleteClient(desc)` if there is a database I/O error, or
+    /// `Error::Other(desc)` if you want to report another error.
+    fn delete_lease(
+        &mut self,
+        client_id: &[u8],
+        time_source: &SystemTimeSource,
+        pool: &mut AddressPool,
+    ) -> Result<(), Error> {
+        self.force_expire_lease(client_id, time_source)?;
+        self.release_lease(client_id, pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// A minimal in-memory `Storage`, for exercising the administrative lease-control
+    /// default methods without a real database.
+    #[derive(Default)]
+    struct InMemoryStorage {
+        clients: HashMap<Ipv4Addr, Vec<u8>>,
+        leases: HashMap<Vec<u8>, Lease>,
+        frozen: BTreeSet<Ipv4Addr>,
+    }
+
+    impl Storage for InMemoryStorage {
+        fn get_client(&self, address: &Ipv4Addr) -> Result<Option<Vec<u8>>, Error> {
+            Ok(self.clients.get(address).cloned())
+        }
+
+        fn add_client(&mut self, address: &Ipv4Addr, client_id: &[u8]) -> Result<(), Error> {
+            self.clients.insert(*address, client_id.to_vec());
+            Ok(())
+        }
+
+        fn delete_client(&mut self, address: &Ipv4Addr) -> Result<(), Error> {
+            self.clients.remove(address);
+            Ok(())
+        }
+
+        fn get_lease(&self, client_id: &[u8]) -> Result<Option<Lease>, Error> {
+            Ok(self.leases.get(client_id).cloned())
+        }
+
+        fn add_lease(&mut self, client_id: &[u8], lease: Lease) -> Result<(), Error> {
+            self.clients.insert(lease.address, client_id.to_vec());
+            self.leases.insert(client_id.to_vec(), lease);
+            Ok(())
+        }
+
+        fn update_lease(
+            &mut self,
+            client_id: &[u8],
+            action: &mut FnMut(&mut Lease) -> (),
+        ) -> Result<Option<Lease>, Error> {
+            match self.leases.get_mut(client_id) {
+                Some(lease) => {
+                    action(lease);
+                    Ok(Some(lease.clone()))
+                }
+                None => Ok(None),
+            }
+        }
+
+        fn check_frozen(&self, address: &Ipv4Addr) -> Result<bool, Error> {
+            Ok(self.frozen.contains(address))
+        }
+
+        fn add_frozen(&mut self, address: &Ipv4Addr) -> Result<(), Error> {
+            self.frozen.insert(*address);
+            Ok(())
+        }
+
+        fn remove_frozen(&mut self, address: &Ipv4Addr) -> Result<(), Error> {
+            self.frozen.remove(address);
+            Ok(())
+        }
+    }
+
+    /// A `SystemTimeSource` fixed at a given instant.
+    struct FixedTime(SystemTime);
+
+    impl SystemTimeSource for FixedTime {
+        fn now(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    fn lease(address: Ipv4Addr, expires: SystemTime) -> Lease {
+        Lease { address, expires }
+    }
+
+    fn single_address_pool() -> AddressPool {
+        let address = Ipv4Addr::new(10, 0, 0, 1);
+        AddressPool::new(address, address)
+    }
+
+    #[test]
+    fn release_lease_disassociates_the_client_and_reclaims_the_address() {
+        let mut storage = InMemoryStorage::default();
+        let mut pool = single_address_pool();
+        let address = pool.allocate(None, None).unwrap();
+        storage.add_lease(b"client", lease(address, UNIX_EPOCH)).unwrap();
+
+        storage.release_lease(b"client", &mut pool).unwrap();
+
+        assert_eq!(storage.get_client(&address).unwrap(), None);
+        assert_eq!(
+            pool.allocate(None, None).unwrap(),
+            address,
+            "address should be reclaimed and immediately reallocatable"
+        );
+    }
+
+    #[test]
+    fn release_lease_is_a_no_op_when_the_client_has_no_lease() {
+        let mut storage = InMemoryStorage::default();
+        let mut pool = single_address_pool();
+
+        storage.release_lease(b"nobody", &mut pool).unwrap();
+
+        assert_eq!(pool.allocate(None, None).unwrap(), Ipv4Addr::new(10, 0, 0, 1));
+    }
+
+    #[test]
+    fn release_lease_by_address_is_a_no_op_when_nothing_is_bound() {
+        let mut storage = InMemoryStorage::default();
+        let mut pool = single_address_pool();
+        let address = Ipv4Addr::new(10, 0, 0, 1);
+
+        storage.release_lease_by_address(&address, &mut pool).unwrap();
+
+        assert_eq!(storage.get_client(&address).unwrap(), None);
+        assert_eq!(pool.allocate(None, None).unwrap(), address);
+    }
+
+    #[test]
+    fn renew_lease_extends_expiry_from_the_time_source() {
+        let mut storage = InMemoryStorage::default();
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        storage.add_lease(b"client", lease(Ipv4Addr::new(10, 0, 0, 1), now)).unwrap();
+        let clock = FixedTime(now);
+
+        let updated = storage.renew_lease(b"client", &clock, Duration::from_secs(60)).unwrap().unwrap();
+
+        assert_eq!(updated.expires, now + Duration::from_secs(60));
+    }
+
+    #[test]
+    fn delete_lease_forces_expiry_before_releasing_unlike_release_lease_alone() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        let far_future = UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let clock = FixedTime(now);
+
+        let mut storage = InMemoryStorage::default();
+        let mut pool = single_address_pool();
+        let address = pool.allocate(None, None).unwrap();
+        storage.add_lease(b"client", lease(address, far_future)).unwrap();
+
+        storage.release_lease(b"client", &mut pool).unwrap();
+
+        assert_eq!(
+            storage.get_lease(b"client").unwrap().unwrap().expires,
+            far_future,
+            "release_lease alone must not touch the lease's expiry"
+        );
+
+        let mut storage = InMemoryStorage::default();
+        let mut pool = single_address_pool();
+        let address = pool.allocate(None, None).unwrap();
+        storage.add_lease(b"client", lease(address, far_future)).unwrap();
+
+        storage.delete_lease(b"client", &clock, &mut pool).unwrap();
+
+        assert_eq!(
+            storage.get_lease(b"client").unwrap().unwrap().expires,
+            now,
+            "delete_lease must force the lease to expire as of now before releasing it"
+        );
+        assert_eq!(storage.get_client(&address).unwrap(), None);
+    }
 }
\ No newline at end of file